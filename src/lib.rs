@@ -6,12 +6,89 @@ use ink_lang2 as ink;
 
 #[ink::contract(version = "0.1.0")]
 mod simple_pns {
+    use ink_core::memory::vec::Vec;
+
+    /// Longest label (one `.`-separated segment) we'll accept.
+    const MAX_LABEL_LEN: usize = 63;
+    /// Longest full name (all labels plus separators) we'll accept.
+    const MAX_NAME_LEN: usize = 255;
+    /// Top-level label length range that counts as "short"/premium; longer
+    /// labels fall into the regular, "long" tier.
+    const SHORT_LABEL_MIN: usize = 3;
+    const SHORT_LABEL_MAX: usize = 6;
+    /// Milliseconds in a year, used to turn a lease duration into an expiry
+    /// timestamp (`block_timestamp` ticks in milliseconds).
+    const YEAR: Timestamp = 365 * 24 * 60 * 60 * 1000;
+    /// Flat yearly rent for a short (premium) name.
+    const SHORT_NAME_RENT_PER_YEAR: Balance = 1_000_000_000_000;
+    /// Flat yearly rent for a long (regular) name.
+    const LONG_NAME_RENT_PER_YEAR: Balance = 100_000_000_000;
+    /// Leases of two years or more are discounted by this percentage.
+    const MULTI_YEAR_DISCOUNT_PERCENT: Balance = 10;
+    /// How long a short-name auction runs before it can be finalized.
+    const AUCTION_DURATION: Timestamp = 3 * 24 * 60 * 60 * 1000;
+    /// A bid landing within this window of the close pushes `end_time` out
+    /// to the bid's time plus this window, so snipers can't win with a
+    /// last-instant bid.
+    const AUCTION_EXTENSION_WINDOW: Timestamp = 10 * 60 * 1000;
+    /// The current storage layout version. Bump this whenever a migration
+    /// is needed and teach `migrate` how to upgrade from the previous one.
+    const CURRENT_STORAGE_VERSION: u32 = 1;
+    /// The account authorized to bootstrap `admin` on a deployment that
+    /// predates the `admin` field, i.e. the account that controls the
+    /// upgrade transaction calling `migrate` for the first time. Must be
+    /// set to the real deploying account before this upgrade goes out -
+    /// whoever holds this key, not whoever calls first, becomes admin.
+    const DEPLOYER: [u8; 32] = [0u8; 32];
+
     #[ink(storage)]
     struct SimplePns {
         /// A hashmap to store all name to addresses mapping.
         name_to_address: storage::HashMap<Hash, AccountId>,
         /// A hashmap to store all name to owners mapping.
         name_to_owner: storage::HashMap<Hash, AccountId>,
+        /// A hashmap to store the label length a name was registered with,
+        /// so renewals can be billed at the same tier as the registration.
+        name_to_label_len: storage::HashMap<Hash, u32>,
+        /// A hashmap to store each name's lease expiry. A name missing here
+        /// has either never been registered or was registered before leases
+        /// existed (see `grandfathered_expiry`).
+        name_to_expiry: storage::HashMap<Hash, Timestamp>,
+        /// The running auction for a short name, if one is in progress.
+        name_to_auction: storage::HashMap<Hash, Auction>,
+        /// Tracks, per short name, whether it has ever been through an
+        /// auction. Once `true` it stays `true`, even after the auction is
+        /// finalized and removed from `name_to_auction`, so a lapsed short
+        /// name can be re-registered directly instead of re-auctioned.
+        short_name_auctioned: storage::HashMap<Hash, bool>,
+        /// The address's chosen primary name, for reverse resolution.
+        address_to_name: storage::HashMap<AccountId, Hash>,
+        /// Balances owed to outbid auction bidders, claimable via
+        /// `withdraw`. Refunds are credited here rather than pushed with
+        /// `self.env().transfer` directly, so a bidder that can't receive
+        /// funds (e.g. a reverting contract account) can't block the
+        /// auction or make the contract eat the new bidder's deposit.
+        pending_withdrawals: storage::HashMap<AccountId, Balance>,
+        /// The account allowed to run `migrate`, set once at deployment.
+        admin: storage::Value<AccountId>,
+        /// The storage layout version this instance is currently on. A
+        /// contract deployed before this field existed reads back as `0`
+        /// (the cell was never written), which is exactly the "pre-lease"
+        /// version `migrate` upgrades from.
+        storage_version: storage::Value<u32>,
+        /// Lease expiry granted, at migration time, to names that were
+        /// registered before leases existed and so have no entry in
+        /// `name_to_expiry`.
+        grandfathered_expiry: storage::Value<Timestamp>,
+    }
+
+    /// An in-progress English auction for a short (premium) name.
+    #[derive(scale::Encode, scale::Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    struct Auction {
+        highest_bid: Balance,
+        highest_bidder: Option<AccountId>,
+        end_time: Timestamp,
     }
 
     /// Emitted whenever a new name is being registered.
@@ -47,26 +124,334 @@ mod simple_pns {
         new_owner: AccountId,
     }
 
+    /// Emitted whenever a name's lease is extended.
+    #[ink(event)]
+    struct Renew {
+        #[ink(topic)]
+        name: Hash,
+        from: AccountId,
+        new_expiry: Timestamp,
+    }
+
+    /// Emitted whenever a lapsed name is reclaimed by a new registration.
+    #[ink(event)]
+    struct Expire {
+        #[ink(topic)]
+        name: Hash,
+    }
+
+    /// Emitted whenever an auction is opened for a short name.
+    #[ink(event)]
+    struct AuctionStarted {
+        #[ink(topic)]
+        name: Hash,
+        opening_price: Balance,
+        end_time: Timestamp,
+    }
+
+    /// Emitted whenever a new highest bid lands on a running auction.
+    #[ink(event)]
+    struct BidPlaced {
+        #[ink(topic)]
+        name: Hash,
+        #[ink(topic)]
+        bidder: AccountId,
+        bid: Balance,
+    }
+
+    /// Emitted whenever an auction is finalized and its name assigned.
+    #[ink(event)]
+    struct AuctionSettled {
+        #[ink(topic)]
+        name: Hash,
+        #[ink(topic)]
+        winner: AccountId,
+        winning_bid: Balance,
+    }
+
+    /// Emitted whenever a parent owner delegates a subdomain.
+    #[ink(event)]
+    struct SetSubnodeOwner {
+        #[ink(topic)]
+        parent: Hash,
+        #[ink(topic)]
+        child: Hash,
+        owner: AccountId,
+    }
+
+    /// Emitted whenever an address's primary name is set.
+    #[ink(event)]
+    struct SetPrimaryName {
+        #[ink(topic)]
+        addr: AccountId,
+        #[ink(topic)]
+        name: Hash,
+    }
+
+    /// Emitted whenever the storage layout is migrated to a new version.
+    #[ink(event)]
+    struct Migrate {
+        from_version: u32,
+        to_version: u32,
+    }
+
+    /// Emitted whenever a pending withdrawal is claimed.
+    #[ink(event)]
+    struct Withdraw {
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+    }
+
     impl SimplePns {
-        /// Creates a new domain name service contract.
+        /// Creates a new domain name service contract. The caller becomes
+        /// the admin allowed to run `migrate`.
         #[ink(constructor)]
         fn new(&mut self) {
+            self.admin.set(self.env().caller());
+            self.storage_version.set(CURRENT_STORAGE_VERSION);
+        }
 
+        /// Upgrade an older deployment's storage to `CURRENT_STORAGE_VERSION`.
+        /// Only the admin may call this, and it refuses to run again once
+        /// the contract is already on the current version.
+        ///
+        /// A deployment from before `admin`/`storage_version` existed has
+        /// both cells unset (`storage_version` reads back as `0`, one less
+        /// than `CURRENT_STORAGE_VERSION`). Bootstrapping `admin` in that
+        /// case is gated on the hardcoded `DEPLOYER` account rather than
+        /// "whoever calls first" - otherwise anyone racing the legitimate
+        /// upgrade transaction could claim `admin` for themselves.
+        #[ink(message)]
+        fn migrate(&mut self) -> bool {
+            let caller = self.env().caller();
+            match self.admin.get().cloned() {
+                Some(admin) => {
+                    if caller != admin {
+                        return false
+                    }
+                }
+                None => {
+                    if caller != AccountId::from(DEPLOYER) {
+                        return false
+                    }
+                    self.admin.set(caller);
+                }
+            }
+            let from_version = self.storage_version.get().cloned().unwrap_or(0);
+            if from_version >= CURRENT_STORAGE_VERSION {
+                return false
+            }
+            // Version 0 -> 1: leases didn't exist yet, so names registered
+            // under it have no `name_to_expiry` entry. Grandfather them in
+            // with a fresh one-year lease starting now instead of treating
+            // them as already expired.
+            self.grandfathered_expiry.set(self.env().block_timestamp() + YEAR);
+            self.storage_version.set(CURRENT_STORAGE_VERSION);
+            self.env().emit_event(Migrate {
+                from_version,
+                to_version: CURRENT_STORAGE_VERSION,
+            });
+            true
+        }
+
+        /// Register specific name with caller as owner for `years` (must be
+        /// at least 1).
+        ///
+        /// `name` is the raw, dotted label (e.g. `b"chainx.dot"`), not a
+        /// pre-hashed value. It is validated against a strict lower-case
+        /// ASCII whitelist before the canonical `Hash` is derived on-chain,
+        /// so two clients always agree on the key for the same label and
+        /// homograph look-alikes (e.g. a Cyrillic `о` standing in for `o`)
+        /// are rejected outright rather than silently stored.
+        ///
+        /// Registration is payable: the caller must transfer at least the
+        /// rent for the name's length tier and the requested lease, or the
+        /// call fails. A name whose previous lease has lapsed is treated as
+        /// free and can be re-registered, emitting `Expire` first.
+        ///
+        /// Short (premium) names can't be claimed this way until they've
+        /// been through an auction at least once; use `start_auction` and
+        /// `bid` for those instead.
+        #[ink(message, payable)]
+        fn register(&mut self, name: Vec<u8>, years: u64) -> bool {
+            if years == 0 || !Self::validate_name(&name) {
+                return false
+            }
+            let hash = Self::hash_name(&name);
+            if self.name_exists(hash) {
+                return false
+            }
+            let label_len = Self::top_label_len(&name);
+            if label_len <= SHORT_LABEL_MAX {
+                if self.short_name_auctioned.get(&hash).cloned() != Some(true) {
+                    return false
+                }
+                // An auction that's merely open (or was never cleaned up
+                // after ending) must be won via `bid`/`finalize_auction`,
+                // not bypassed by registering directly the moment it opens.
+                if self.name_to_auction.get(&hash).is_some() {
+                    return false
+                }
+            }
+            let fee = Self::rent_fee(label_len, years);
+            if self.env().transferred_balance() < fee {
+                return false
+            }
+            if self.name_to_owner.get(&hash).is_some() {
+                self.env().emit_event(Expire { name: hash });
+            }
+            let caller = self.env().caller();
+            self.name_to_owner.insert(hash, caller);
+            self.name_to_label_len.insert(hash, label_len as u32);
+            let expiry = self.env().block_timestamp() + years * YEAR;
+            self.name_to_expiry.insert(hash, expiry);
+            self.env().emit_event(Register { name: hash, from: caller });
+            true
+        }
+
+        /// Extend the lease on a name the caller owns by `periods` years.
+        /// The renewed lease starts from the later of "now" and the
+        /// current expiry, so renewing early doesn't lose the remaining
+        /// time.
+        #[ink(message, payable)]
+        fn renew(&mut self, name: Hash, periods: u64) -> bool {
+            if periods == 0 {
+                return false
+            }
+            let caller = self.env().caller();
+            let owner = self.name_to_owner.get(&name).cloned();
+            if Some(caller) != owner {
+                return false
+            }
+            let label_len = match self.name_to_label_len.get(&name) {
+                Some(len) => *len as usize,
+                None => return false,
+            };
+            let fee = Self::rent_fee(label_len, periods);
+            if self.env().transferred_balance() < fee {
+                return false
+            }
+            let now = self.env().block_timestamp();
+            let current_expiry = self.name_to_expiry.get(&name).cloned().unwrap_or(now);
+            let base = if current_expiry > now { current_expiry } else { now };
+            let new_expiry = base + periods * YEAR;
+            self.name_to_expiry.insert(name, new_expiry);
+            self.env().emit_event(Renew { name, from: caller, new_expiry });
+            true
+        }
+
+        /// Open an English auction for a short, unregistered name. The
+        /// opening price is one year's rent and there is no reserve.
+        #[ink(message)]
+        fn start_auction(&mut self, name: Vec<u8>) -> bool {
+            if !Self::validate_name(&name) {
+                return false
+            }
+            let label_len = Self::top_label_len(&name);
+            if label_len < SHORT_LABEL_MIN || label_len > SHORT_LABEL_MAX {
+                return false
+            }
+            let hash = Self::hash_name(&name);
+            if self.name_exists(hash) || self.name_to_auction.get(&hash).is_some() {
+                return false
+            }
+            let opening_price = Self::rent_fee(label_len, 1);
+            let end_time = self.env().block_timestamp() + AUCTION_DURATION;
+            self.name_to_auction.insert(hash, Auction {
+                highest_bid: opening_price,
+                highest_bidder: None,
+                end_time,
+            });
+            self.name_to_label_len.insert(hash, label_len as u32);
+            self.short_name_auctioned.insert(hash, true);
+            self.env().emit_event(AuctionStarted { name: hash, opening_price, end_time });
+            true
+        }
+
+        /// Place a bid on a running auction. Must exceed the current
+        /// highest bid; the previous highest bidder, if any, has their bid
+        /// credited to `pending_withdrawals` (claim it via `withdraw`). A
+        /// bid landing near the close extends `end_time`.
+        #[ink(message, payable)]
+        fn bid(&mut self, name: Hash) -> bool {
+            let mut auction = match self.name_to_auction.get(&name).cloned() {
+                Some(auction) => auction,
+                None => return false,
+            };
+            let now = self.env().block_timestamp();
+            if now > auction.end_time {
+                return false
+            }
+            let value = self.env().transferred_balance();
+            if value <= auction.highest_bid {
+                return false
+            }
+            if let Some(previous_bidder) = auction.highest_bidder {
+                let owed = self.pending_withdrawals.get(&previous_bidder).cloned().unwrap_or(0);
+                self.pending_withdrawals.insert(previous_bidder, owed + auction.highest_bid);
+            }
+            let caller = self.env().caller();
+            auction.highest_bid = value;
+            auction.highest_bidder = Some(caller);
+            if auction.end_time - now < AUCTION_EXTENSION_WINDOW {
+                auction.end_time = now + AUCTION_EXTENSION_WINDOW;
+            }
+            self.name_to_auction.insert(name, auction);
+            self.env().emit_event(BidPlaced { name, bidder: caller, bid: value });
+            true
         }
 
-        /// Register specific name with caller as owner.
+        /// Claim any balance owed to the caller from being outbid in an
+        /// auction.
         #[ink(message)]
-        fn register(&mut self, name: Hash) -> bool {
+        fn withdraw(&mut self) -> bool {
             let caller = self.env().caller();
-            if self.name_exists(name) {
+            let amount = self.pending_withdrawals.get(&caller).cloned().unwrap_or(0);
+            if amount == 0 {
+                return false
+            }
+            self.pending_withdrawals.insert(caller, 0);
+            if self.env().transfer(caller, amount).is_err() {
+                // Restore the owed balance so the caller can retry later.
+                self.pending_withdrawals.insert(caller, amount);
+                return false
+            }
+            self.env().emit_event(Withdraw { to: caller, amount });
+            true
+        }
+
+        /// Finalize a closed auction: the highest bidder becomes the
+        /// name's owner with a fresh one-year lease. If nobody ever bid,
+        /// the name is simply freed up for a new auction.
+        #[ink(message)]
+        fn finalize_auction(&mut self, name: Hash) -> bool {
+            let auction = match self.name_to_auction.get(&name).cloned() {
+                Some(auction) => auction,
+                None => return false,
+            };
+            if self.env().block_timestamp() <= auction.end_time {
                 return false
             }
-            self.name_to_owner.insert(name, caller);
-            self.env().emit_event(Register { name, from: caller });
+            self.name_to_auction.remove(&name);
+            let winner = match auction.highest_bidder {
+                Some(winner) => winner,
+                None => return false,
+            };
+            self.name_to_owner.insert(name, winner);
+            let expiry = self.env().block_timestamp() + YEAR;
+            self.name_to_expiry.insert(name, expiry);
+            self.env().emit_event(AuctionSettled {
+                name,
+                winner,
+                winning_bid: auction.highest_bid,
+            });
             true
         }
 
-        /// Set address for specific name.
+        /// Set address for specific name. Fails once the name's lease has
+        /// lapsed - a stale owner can't keep exercising control over a
+        /// name `name_exists`/`get_address` already treat as free.
         #[ink(message)]
         fn set_address(&mut self, name: Hash, new_address: AccountId) -> bool {
             let caller = self.env().caller();
@@ -77,7 +462,17 @@ mod simple_pns {
             if Some(caller) != owner {
                 return false
             }
+            if self.is_expired(name) {
+                return false
+            }
             let old_address = self.name_to_address.insert(name, new_address);
+            // The reverse record for whoever used to resolve from `name`
+            // is now stale - the name no longer points at them.
+            if let Some(old_address) = old_address {
+                if self.address_to_name.get(&old_address).cloned() == Some(name) {
+                    self.address_to_name.remove(&old_address);
+                }
+            }
             self.env().emit_event(SetAddress {
                 name,
                 from: caller,
@@ -87,7 +482,8 @@ mod simple_pns {
             true
         }
 
-        /// Transfer owner to another address.
+        /// Transfer owner to another address. Fails once the name's lease
+        /// has lapsed, for the same reason as `set_address`.
         #[ink(message)]
         fn transfer(&mut self, name: Hash, to: AccountId) -> bool {
             let caller = self.env().caller();
@@ -98,7 +494,17 @@ mod simple_pns {
             if Some(caller) != owner {
                 return false
             }
+            if self.is_expired(name) {
+                return false
+            }
             let old_owner = self.name_to_owner.insert(name, to);
+            // The previous owner may no longer use `name` as their primary
+            // name now that they don't own it.
+            if let Some(old_owner) = old_owner {
+                if self.address_to_name.get(&old_owner).cloned() == Some(name) {
+                    self.address_to_name.remove(&old_owner);
+                }
+            }
             self.env().emit_event(Transfer {
                 name,
                 from: caller,
@@ -108,9 +514,74 @@ mod simple_pns {
             true
         }
 
-        /// Get address for specific name.
+        /// Set `name` as the caller's primary name, for reverse
+        /// resolution. The caller must both own `name` and have it
+        /// currently resolving to their own address; a lapsed lease fails
+        /// this like every other owner-gated message.
+        #[ink(message)]
+        fn set_primary_name(&mut self, name: Hash) -> bool {
+            let caller = self.env().caller();
+            if self.name_to_owner.get(&name).cloned() != Some(caller) {
+                return false
+            }
+            if self.is_expired(name) {
+                return false
+            }
+            if self.get_address(name) != Some(caller) {
+                return false
+            }
+            self.address_to_name.insert(caller, name);
+            self.env().emit_event(SetPrimaryName { addr: caller, name });
+            true
+        }
+
+        /// Get the primary name for `addr`, if one is set.
+        #[ink(message)]
+        fn get_name(&self, addr: AccountId) -> Option<Hash> {
+            self.address_to_name.get(&addr).cloned()
+        }
+
+        /// Delegate a subdomain of a name the caller owns to `owner`,
+        /// without giving up the parent. The child's key is the ENS-style
+        /// namehash recurrence `hash(parent ++ hash(label))`, so resolving
+        /// it later is just another `get_address`/`name_to_owner` lookup
+        /// on the derived hash - no change needed elsewhere. Fails if the
+        /// parent's lease has lapsed.
+        ///
+        /// The child gets its own `name_to_expiry` entry: the parent's
+        /// recorded expiry if there is one; otherwise, for a pre-lease
+        /// parent, `grandfathered_expiry` (the parent's own effective
+        /// expiry, which has not passed since the parent is not expired);
+        /// otherwise a fresh one-year lease.
+        #[ink(message)]
+        fn set_subnode_owner(&mut self, parent: Hash, label: Vec<u8>, owner: AccountId) -> bool {
+            if !Self::validate_label(&label) {
+                return false
+            }
+            let caller = self.env().caller();
+            if self.name_to_owner.get(&parent).cloned() != Some(caller) {
+                return false
+            }
+            if self.is_expired(parent) {
+                return false
+            }
+            let child = Self::namehash(parent, &label);
+            self.name_to_owner.insert(child, owner);
+            let child_expiry = self.name_to_expiry.get(&parent).cloned()
+                .or_else(|| self.grandfathered_expiry.get().cloned())
+                .unwrap_or_else(|| self.env().block_timestamp() + YEAR);
+            self.name_to_expiry.insert(child, child_expiry);
+            self.env().emit_event(SetSubnodeOwner { parent, child, owner });
+            true
+        }
+
+        /// Get address for specific name. A name whose lease has lapsed is
+        /// unresolvable.
         #[ink(message)]
         fn get_address(&self, name: Hash) -> Option<AccountId> {
+            if self.is_expired(name) {
+                return None
+            }
             self.name_to_address.get(&name).cloned()
         }
 
@@ -120,16 +591,343 @@ mod simple_pns {
             self.name_exists(name)
         }
 
+        /// A name exists if it has an owner and its lease has not lapsed.
         fn name_exists(&self, name: Hash) -> bool {
-            if self.name_to_owner.get(&name).is_some() {
-                return true
+            self.name_to_owner.get(&name).is_some() && !self.is_expired(name)
+        }
+
+        /// A name with no recorded expiry is either free (never registered)
+        /// or a pre-lease registration grandfathered in by `migrate`.
+        fn is_expired(&self, name: Hash) -> bool {
+            match self.name_to_expiry.get(&name) {
+                Some(expiry) => self.env().block_timestamp() > *expiry,
+                None => match (self.name_to_owner.get(&name), self.grandfathered_expiry.get()) {
+                    (Some(_), Some(expiry)) => self.env().block_timestamp() > *expiry,
+                    _ => false,
+                },
+            }
+        }
+
+        /// The length of a name's top-level label, i.e. everything up to
+        /// the first `.` (or the whole name if it has no hierarchy). Used
+        /// to pick the rent tier.
+        fn top_label_len(name: &[u8]) -> usize {
+            name.split(|b| *b == b'.').next().map(|l| l.len()).unwrap_or(0)
+        }
+
+        /// Yearly rent for a name whose top-level label is `label_len`
+        /// bytes long: the short/premium tier costs more than the regular,
+        /// long tier.
+        fn rent_per_year(label_len: usize) -> Balance {
+            if label_len <= SHORT_LABEL_MAX {
+                SHORT_NAME_RENT_PER_YEAR
+            } else {
+                LONG_NAME_RENT_PER_YEAR
+            }
+        }
+
+        /// Total rent for leasing a name of `label_len` bytes for `years`
+        /// years, with a flat discount applied to multi-year leases.
+        fn rent_fee(label_len: usize, years: u64) -> Balance {
+            let base = Self::rent_per_year(label_len) * years as Balance;
+            if years >= 2 {
+                base - base * MULTI_YEAR_DISCOUNT_PERCENT / 100
+            } else {
+                base
+            }
+        }
+
+        /// Returns `true` if `byte` may appear in a label: `a`-`z`, `0`-`9`
+        /// or the `.` hierarchy separator.
+        fn is_allowed_byte(byte: u8) -> bool {
+            match byte {
+                b'a'..=b'z' | b'0'..=b'9' | b'.' => true,
+                _ => false,
+            }
+        }
+
+        /// Validates a raw name: not overlong, drawn only from the
+        /// `.abcdefghijklmnopqrstuvwxyz0123456789` charset, and free of
+        /// leading, trailing or doubled `.` separators. Each `.`-separated
+        /// label is checked individually so e.g. `polka..dot` is rejected.
+        /// Labels shorter than `SHORT_LABEL_MIN` are rejected outright: the
+        /// short/premium tier starts there, and anything below it would be
+        /// neither directly registrable nor auctionable.
+        fn validate_name(name: &[u8]) -> bool {
+            if name.is_empty() || name.len() > MAX_NAME_LEN {
+                return false
+            }
+            if !name.iter().all(|b| Self::is_allowed_byte(*b)) {
+                return false
+            }
+            for label in name.split(|b| *b == b'.') {
+                if !Self::validate_label(label) {
+                    return false
+                }
             }
-            false
+            true
+        }
+
+        /// Validates a single label (no `.` hierarchy): within the
+        /// `SHORT_LABEL_MIN..=MAX_LABEL_LEN` length range, drawn only from
+        /// the allowed charset.
+        fn validate_label(label: &[u8]) -> bool {
+            label.len() >= SHORT_LABEL_MIN
+                && label.len() <= MAX_LABEL_LEN
+                && label.iter().all(|b| *b != b'.' && Self::is_allowed_byte(*b))
+        }
+
+        /// Hashes already-validated, normalized name bytes into the
+        /// canonical on-chain `Hash` key.
+        fn hash_name(name: &[u8]) -> Hash {
+            Self::hash_bytes(name)
+        }
+
+        /// The ENS-style namehash recurrence: the child of `parent` labeled
+        /// `label` is `hash(parent ++ hash(label))`. The root's parent is
+        /// the zero hash.
+        fn namehash(parent: Hash, label: &[u8]) -> Hash {
+            let label_hash = Self::hash_bytes(label);
+            let mut buf: Vec<u8> = Vec::with_capacity(64);
+            buf.extend_from_slice(parent.as_ref());
+            buf.extend_from_slice(label_hash.as_ref());
+            Self::hash_bytes(&buf)
+        }
+
+        /// Hashes arbitrary bytes into a `Hash`.
+        fn hash_bytes(data: &[u8]) -> Hash {
+            let mut output = [0u8; 32];
+            ink_core::env::hash::blake2b_256(data, &mut output);
+            Hash::from(output)
         }
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
+
+        type Types = ink_core::env::DefaultSrmlTypes;
+
+        fn account(seed: u8) -> AccountId {
+            AccountId::from([seed; 32])
+        }
+
+        #[test]
+        fn start_auction_only_accepts_short_unauctioned_names() {
+            let mut contract = SimplePns::new();
+            assert!(contract.start_auction(b"xyz".to_vec()));
+            // Already auctioning.
+            assert!(!contract.start_auction(b"xyz".to_vec()));
+            // Too long for the short/premium tier.
+            assert!(!contract.start_auction(b"longlabel".to_vec()));
+        }
+
+        #[test]
+        fn register_refuses_a_short_name_with_an_open_auction() {
+            let mut contract = SimplePns::new();
+            assert!(contract.start_auction(b"xyz".to_vec()));
+
+            // The auction is open (and `short_name_auctioned` is already
+            // `true`), but nobody has won it yet - direct registration
+            // must still be refused.
+            assert!(!contract.register(b"xyz".to_vec(), 1));
+        }
+
+        #[test]
+        fn auction_lifecycle_refunds_outbid_bidder_and_settles_to_winner() {
+            let mut contract = SimplePns::new();
+            let accounts = ink_core::env::test::default_accounts::<Types>();
+
+            assert!(contract.start_auction(b"xyz".to_vec()));
+            let name = SimplePns::hash_name(b"xyz");
+            let opening_price = contract.name_to_auction.get(&name).cloned().unwrap().highest_bid;
+
+            ink_core::env::test::set_caller::<Types>(accounts.alice);
+            ink_core::env::test::set_value_transferred::<Types>(opening_price + 1);
+            assert!(contract.bid(name));
+
+            // Bob outbids Alice; her bid is credited to pending_withdrawals
+            // instead of being pushed straight back to her account.
+            ink_core::env::test::set_caller::<Types>(accounts.bob);
+            ink_core::env::test::set_value_transferred::<Types>(opening_price + 2);
+            assert!(contract.bid(name));
+            assert_eq!(
+                contract.pending_withdrawals.get(&accounts.alice).cloned(),
+                Some(opening_price + 1),
+            );
+
+            ink_core::env::test::set_block_timestamp::<Types>(AUCTION_DURATION + 1);
+            assert!(contract.finalize_auction(name));
+            assert_eq!(contract.name_to_owner.get(&name).cloned(), Some(accounts.bob));
+        }
+
+        #[test]
+        fn parent_owner_can_delegate_a_subnode() {
+            let mut contract = SimplePns::new();
+            let alice = account(0x1);
+            let bob = account(0x2);
+
+            let parent = SimplePns::namehash(Hash::default(), b"polka");
+            contract.name_to_owner.insert(parent, alice);
+
+            assert!(contract.set_subnode_owner(parent, b"chainx".to_vec(), bob));
+            let child = SimplePns::namehash(parent, b"chainx");
+            assert_eq!(contract.name_to_owner.get(&child).cloned(), Some(bob));
+        }
+
+        #[test]
+        fn validate_name_rejects_stray_dots() {
+            assert!(!SimplePns::validate_name(b".chainx.dot"));
+            assert!(!SimplePns::validate_name(b"chainx.dot."));
+            assert!(!SimplePns::validate_name(b"chainx..dot"));
+        }
+
+        #[test]
+        fn validate_name_rejects_out_of_charset_bytes() {
+            assert!(!SimplePns::validate_name(b"Chainx.dot"));
+            // Cyrillic "о" standing in for Latin "o".
+            assert!(!SimplePns::validate_name("chainx.d\u{043E}t".as_bytes()));
+        }
+
+        #[test]
+        fn validate_name_accepts_a_well_formed_name() {
+            assert!(SimplePns::validate_name(b"chainx.dot"));
+        }
+
+        #[test]
+        fn rent_fee_short_tier_costs_more_than_long_tier() {
+            assert!(SimplePns::rent_fee(SHORT_LABEL_MAX, 1) > SimplePns::rent_fee(SHORT_LABEL_MAX + 1, 1));
+        }
+
+        #[test]
+        fn rent_fee_applies_multi_year_discount() {
+            let one_year = SimplePns::rent_fee(6, 1);
+            let two_years = SimplePns::rent_fee(6, 2);
+            assert_eq!(
+                two_years,
+                one_year * 2 - (one_year * 2 * MULTI_YEAR_DISCOUNT_PERCENT / 100)
+            );
+            assert!(two_years < one_year * 2);
+        }
+
+        #[test]
+        fn migrate_bootstraps_admin_on_an_old_deployment_then_refuses_twice() {
+            // Simulate a deployment from before `admin`/`storage_version`
+            // existed: every field defaults, so neither cell was ever
+            // written, same as an already-deployed contract upgraded to
+            // this code in place.
+            let mut contract = SimplePns {
+                name_to_address: Default::default(),
+                name_to_owner: Default::default(),
+                name_to_label_len: Default::default(),
+                name_to_expiry: Default::default(),
+                name_to_auction: Default::default(),
+                short_name_auctioned: Default::default(),
+                address_to_name: Default::default(),
+                pending_withdrawals: Default::default(),
+                admin: Default::default(),
+                storage_version: Default::default(),
+                grandfathered_expiry: Default::default(),
+            };
+            let deployer = AccountId::from(DEPLOYER);
+
+            ink_core::env::test::set_caller::<Types>(deployer);
+            assert!(contract.migrate());
+            assert_eq!(contract.admin.get().cloned(), Some(deployer));
+
+            // Already on the current version: refuses to run again.
+            assert!(!contract.migrate());
+        }
+
+        #[test]
+        fn migrate_refuses_to_bootstrap_admin_for_anyone_but_the_deployer() {
+            // Same unmigrated-deployment shape as above, but this time the
+            // first caller isn't the hardcoded `DEPLOYER` account - they
+            // must not be able to race the real upgrade transaction and
+            // claim `admin` for themselves.
+            let mut contract = SimplePns {
+                name_to_address: Default::default(),
+                name_to_owner: Default::default(),
+                name_to_label_len: Default::default(),
+                name_to_expiry: Default::default(),
+                name_to_auction: Default::default(),
+                short_name_auctioned: Default::default(),
+                address_to_name: Default::default(),
+                pending_withdrawals: Default::default(),
+                admin: Default::default(),
+                storage_version: Default::default(),
+                grandfathered_expiry: Default::default(),
+            };
+            let accounts = ink_core::env::test::default_accounts::<Types>();
+
+            ink_core::env::test::set_caller::<Types>(accounts.bob);
+            assert!(!contract.migrate());
+            assert_eq!(contract.admin.get().cloned(), None);
+        }
+
+        #[test]
+        fn transfer_clears_a_stale_primary_name() {
+            let mut contract = SimplePns::new();
+            let accounts = ink_core::env::test::default_accounts::<Types>();
+            let name = SimplePns::hash_name(b"chainx");
+
+            contract.name_to_owner.insert(name, accounts.alice);
+            contract.name_to_address.insert(name, accounts.alice);
+            contract.address_to_name.insert(accounts.alice, name);
+
+            ink_core::env::test::set_caller::<Types>(accounts.alice);
+            assert!(contract.transfer(name, accounts.bob));
+            assert_eq!(contract.get_name(accounts.alice), None);
+        }
+
+        #[test]
+        fn set_address_clears_a_stale_primary_name() {
+            let mut contract = SimplePns::new();
+            let accounts = ink_core::env::test::default_accounts::<Types>();
+            let name = SimplePns::hash_name(b"chainx");
+
+            contract.name_to_owner.insert(name, accounts.alice);
+            contract.name_to_address.insert(name, accounts.alice);
+            contract.address_to_name.insert(accounts.alice, name);
+
+            ink_core::env::test::set_caller::<Types>(accounts.alice);
+            assert!(contract.set_address(name, accounts.bob));
+            assert_eq!(contract.get_name(accounts.alice), None);
+        }
+
+        #[test]
+        fn delegated_subnode_inherits_parent_expiry_not_a_stale_grandfather() {
+            let mut contract = SimplePns::new();
+            let alice = account(0x1);
+            let bob = account(0x2);
+
+            let parent = SimplePns::namehash(Hash::default(), b"polka");
+            contract.name_to_owner.insert(parent, alice);
+            contract.name_to_expiry.insert(parent, 1_000_000);
+
+            // A long-past migration timestamp must not leak into a subnode
+            // delegated well after it.
+            contract.grandfathered_expiry.set(1);
+
+            assert!(contract.set_subnode_owner(parent, b"chainx".to_vec(), bob));
+            let child = SimplePns::namehash(parent, b"chainx");
+            assert_eq!(contract.name_to_expiry.get(&child).cloned(), Some(1_000_000));
+        }
+
+        #[test]
+        fn non_owner_cannot_delegate_a_subnode() {
+            let mut contract = SimplePns::new();
+            let bob = account(0x2);
+            let carol = account(0x3);
+
+            // The default test caller doesn't own `parent` (`bob` does),
+            // so delegating must fail and leave the child unset.
+            let parent = SimplePns::namehash(Hash::default(), b"polka");
+            contract.name_to_owner.insert(parent, bob);
+
+            assert!(!contract.set_subnode_owner(parent, b"chainx".to_vec(), carol));
+            let child = SimplePns::namehash(parent, b"chainx");
+            assert_eq!(contract.name_to_owner.get(&child), None);
+        }
     }
 }